@@ -0,0 +1,162 @@
+//! ABI-related types shared across all of Winch's supported targets.
+//!
+//! Every target backend implements the [`ABI`] trait, which answers the
+//! questions the rest of the code generator needs in order to lower a
+//! function call or a function's prologue/epilogue: how arguments and
+//! results are assigned to registers or the stack, what alignment the
+//! call frame requires, and so on. The actual argument/result assignment
+//! for a particular signature is precomputed once into an [`ABISig`] and
+//! reused for the lifetime of a call site.
+
+use crate::reg::Reg;
+
+/// The Wasm value types the ABI layer needs to reason about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmType {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl WasmType {
+    /// The size of a value of this type, in bytes.
+    pub fn size(&self) -> u32 {
+        match self {
+            WasmType::I32 | WasmType::F32 => 4,
+            WasmType::I64 | WasmType::F64 => 8,
+        }
+    }
+}
+
+/// Round `bytes` up to the next multiple of `alignment`.
+///
+/// `alignment` must be a power of two.
+pub fn align_to(bytes: u32, alignment: u32) -> u32 {
+    debug_assert!(alignment.is_power_of_two());
+    (bytes + (alignment - 1)) & !(alignment - 1)
+}
+
+/// Calculate the adjustment needed, if any, so that `sp_offset` plus
+/// `arg_base_offset` lands on a `call_stack_align`-aligned boundary.
+pub fn calculate_frame_adjustment(sp_offset: u32, arg_base_offset: u32, call_stack_align: u32) -> u32 {
+    let addend = sp_offset + arg_base_offset;
+    align_to(addend, call_stack_align) - addend
+}
+
+/// How a sub-word argument must be extended to fill the rest of its
+/// register or stack slot.
+///
+/// Wasm itself has no sub-word value types, but some callees -- host
+/// calls and libcalls declared with narrower C-ABI parameter types in
+/// particular -- require the high bits of an argument to be a
+/// well-defined sign- or zero-extension of its low bits, rather than
+/// left as whatever garbage the producing instruction happened to
+/// leave there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArgumentExtension {
+    /// The argument's high bits are already well-defined; don't extend.
+    None,
+    /// Zero-extend the argument to fill its slot.
+    Zero,
+    /// Sign-extend the argument to fill its slot.
+    Sign,
+}
+
+/// A single argument location, as assigned by the ABI.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ABIArg {
+    /// The argument is passed in a register.
+    Reg {
+        ty: WasmType,
+        reg: Reg,
+        extension: ArgumentExtension,
+    },
+    /// The argument is passed on the stack, at the given offset from the
+    /// callee's incoming argument base.
+    Stack {
+        ty: WasmType,
+        offset: u32,
+        extension: ArgumentExtension,
+    },
+}
+
+/// The location(s) a callee's results are returned in.
+#[derive(Debug, Clone)]
+pub(crate) enum ABIResult {
+    /// No results.
+    Void,
+    /// A single result, returned in a register.
+    Reg { ty: WasmType, reg: Reg },
+    /// Results that don't fit in the available return registers are
+    /// written by the callee into a caller-allocated return area, whose
+    /// address is passed as a hidden first argument. Any results that do
+    /// fit in return registers are still returned there; `area_size` only
+    /// accounts for the portion spilled to memory.
+    Area {
+        /// The types of every result, in order, regardless of whether a
+        /// given result ends up in a register or in the return area.
+        tys: Vec<WasmType>,
+        /// The register results at the front of `tys`, if any.
+        regs: Vec<Reg>,
+        /// The size, in bytes, of the return area backing the remaining
+        /// results.
+        area_size: u32,
+    },
+}
+
+impl ABIResult {
+    /// Whether the callee returns no results.
+    pub(crate) fn is_void(&self) -> bool {
+        matches!(self, ABIResult::Void)
+    }
+
+    /// The size, in bytes, of the caller-allocated return area needed for
+    /// this result, or `0` if every result fits in registers.
+    pub(crate) fn area_size(&self) -> u32 {
+        match self {
+            ABIResult::Area { area_size, .. } => *area_size,
+            _ => 0,
+        }
+    }
+}
+
+/// The fully-resolved ABI signature of a function, computed once from its
+/// Wasm type and reused at every call site.
+pub(crate) struct ABISig {
+    /// The assigned location of every parameter.
+    pub(crate) params: Vec<ABIArg>,
+    /// The assigned location(s) of the results.
+    pub(crate) result: ABIResult,
+    /// The total size, in bytes, of the stack-passed argument area
+    /// (excluding the return-area pointer, if any).
+    pub(crate) stack_bytes: u32,
+}
+
+/// Target-specific ABI information needed to lower calls.
+pub(crate) trait ABI {
+    /// The register used to hold the return-area pointer, when the
+    /// callee's results don't all fit in return registers.
+    fn ret_area_ptr_reg() -> Reg;
+
+    /// The register reserved for use as a scratch register while
+    /// assigning arguments.
+    fn scratch_reg() -> Reg;
+
+    /// The size, in bytes, of a machine word on this target.
+    fn word_bytes() -> u32;
+
+    /// The offset, from the stack pointer at the callsite, at which the
+    /// callee's incoming stack arguments begin.
+    fn arg_base_offset(&self) -> u32;
+
+    /// The required alignment, in bytes, of the stack pointer at a call
+    /// boundary.
+    fn call_stack_align(&self) -> u8;
+
+    /// The size, in bytes, of the OS guard page installed past the end
+    /// of the stack, used to decide when a call's outgoing-argument
+    /// allocation needs to be probed one page at a time rather than
+    /// reserved in one jump.
+    fn guard_size(&self) -> u32;
+}
@@ -1,8 +1,11 @@
 //! Function call emission.  For more details around the ABI and
 //! calling convention, see [ABI].
-use super::CodeGenContext;
+use super::{
+    error::{CodegenError, CodegenResult, MAX_CALL_STACK_ARGS_SIZE},
+    CodeGenContext,
+};
 use crate::{
-    abi::{align_to, calculate_frame_adjustment, ABIArg, ABIResult, ABISig, ABI},
+    abi::{align_to, calculate_frame_adjustment, ABIArg, ABIResult, ABISig, ArgumentExtension, ABI},
     masm::{CalleeKind, MacroAssembler, OperandSize},
     reg::Reg,
     stack::Val,
@@ -65,6 +68,44 @@ pub(crate) struct FnCall<'a> {
     /// stack, including any adjustments to the function's frame and
     /// aligned to to the required ABI alignment.
     arg_stack_space: u32,
+    /// The size, in bytes, of the return-area reserved in the caller's
+    /// frame, or `0` if the callee's results all fit in return
+    /// registers.
+    ///
+    /// This area is always the innermost (closest-to-`sp`) part of the
+    /// call's stack footprint, i.e. at `address_at_sp(0)`, and its
+    /// address is handed to the callee as a hidden first argument,
+    /// following the "vanilla ABI" convention: the callee writes any
+    /// result that doesn't fit in a return register into this area, in
+    /// order. It's reclaimed once `handle_result` has read every result
+    /// back out of it.
+    ret_area_size: u32,
+    /// Whether this call's stack arguments live in a shared
+    /// outgoing-argument area allocated once in the function's prologue
+    /// ("accumulated outgoing args" mode, see
+    /// [`CodeGenContext::outgoing_args_area`]) rather than being
+    /// reserved and freed by this call on its own.
+    ///
+    /// When `true`, `arg_stack_space` is still computed but is left out
+    /// of `total_stack_space`, and `emit` does not
+    /// `reserve_stack`/`free_stack` it; argument offsets come from
+    /// [`CodeGenContext::outgoing_args_area`] instead, and the shared
+    /// block is sized by [`FnCall::max_outgoing_args_size`].
+    uses_shared_outgoing_args: bool,
+    /// `Some(offset)` when this is a tail call, i.e. a call in return
+    /// position whose stack-argument area fits within the caller's own
+    /// incoming argument area (see [`FnCall::tail_call_arg_space`]).
+    /// `offset` is the ABI's `arg_base_offset` plus the `sp_offset` at
+    /// setup: the distance from the current machine `sp` to the start
+    /// of the caller's incoming stack arguments, which the tail call
+    /// overwrites in place rather than
+    /// allocating a fresh outgoing-argument area for. `None` for a
+    /// normal call.
+    tail_call_base_offset: Option<u32>,
+    /// The target's guard page size, used by `emit` to decide whether
+    /// the outgoing-argument allocation needs probing before the stack
+    /// pointer is moved across it.
+    guard_size: u32,
     /// The ABI-specific signature of the callee.
     abi_sig: &'a ABISig,
     /// The stack pointer offset prior to preparing and emitting the
@@ -86,12 +127,17 @@ impl<'a> FnCall<'a> {
     /// want to calculate any adjustments to the caller's frame, after
     /// having saved any live registers, so that we can account for
     /// any pushes generated by register spilling.
+    ///
+    /// Returns [`CodegenError::ImplLimitExceeded`] if the callee's
+    /// combined stack-argument and return-area size exceeds
+    /// [`MAX_CALL_STACK_ARGS_SIZE`], rather than risk the `u32`
+    /// arithmetic below wrapping and producing a corrupt stack layout.
     pub fn new<A: ABI, M: MacroAssembler>(
         abi: &A,
         callee_sig: &'a ABISig,
         context: &mut CodeGenContext,
         masm: &mut M,
-    ) -> Self {
+    ) -> CodegenResult<Self> {
         let stack = &context.stack;
         let arg_stack_space = callee_sig.stack_bytes;
         let callee_params = &callee_sig.params;
@@ -141,15 +187,201 @@ impl<'a> FnCall<'a> {
             abi.call_stack_align() as u32,
         );
 
-        let arg_stack_space = align_to(arg_stack_space + delta, abi.call_stack_align() as u32);
-        Self {
+        // Validate with widened arithmetic before performing the `u32`
+        // addition below, which would otherwise risk overflowing for a
+        // pathological `callee_sig.stack_bytes` near `u32::MAX`.
+        let arg_stack_space = align_to(
+            Self::checked_stack_add(arg_stack_space, delta)?,
+            abi.call_stack_align() as u32,
+        );
+        // Any results that don't fit in the callee's return registers
+        // need a caller-allocated area to be written into; reserve it
+        // here, aligned like the rest of the call's stack footprint, so
+        // it can be folded into a single `reserve_stack`/`free_stack`
+        // pair alongside the outgoing arguments. Validate before
+        // `align_to`'s addition, which would otherwise risk overflowing
+        // for a pathological `area_size` near `u32::MAX`.
+        let ret_area_size = align_to(
+            Self::checked_stack_add(callee_sig.result.area_size(), 0)?,
+            abi.call_stack_align() as u32,
+        );
+        Self::check_stack_area_size(arg_stack_space, ret_area_size)?;
+        let uses_shared_outgoing_args = context.outgoing_args_area.is_some();
+        Ok(Self {
             abi_sig: &callee_sig,
             arg_stack_space,
+            ret_area_size,
+            uses_shared_outgoing_args,
+            tail_call_base_offset: None,
+            guard_size: abi.guard_size(),
             total_stack_space: (spilled_regs * <A as ABI>::word_bytes())
                 + (memory_values * <A as ABI>::word_bytes())
-                + arg_stack_space,
+                + if uses_shared_outgoing_args { 0 } else { arg_stack_space }
+                + ret_area_size,
             sp_offset_at_callsite,
+        })
+    }
+
+    /// Add `a` and `b`, widened to `u64` so the addition itself can't
+    /// silently wrap, rejecting the result with
+    /// [`CodegenError::ImplLimitExceeded`] if it would exceed
+    /// [`MAX_CALL_STACK_ARGS_SIZE`] rather than hand back a `u32` that
+    /// overflowed.
+    fn checked_stack_add(a: u32, b: u32) -> CodegenResult<u32> {
+        let combined = (a as u64) + (b as u64);
+        if combined > MAX_CALL_STACK_ARGS_SIZE as u64 {
+            return Err(CodegenError::ImplLimitExceeded);
         }
+        Ok(combined as u32)
+    }
+
+    /// Reject a call whose combined stack-argument and return-area size
+    /// would exceed [`MAX_CALL_STACK_ARGS_SIZE`]. Checked with `u64`
+    /// arithmetic so that the check itself can't be fooled by `u32`
+    /// wraparound.
+    fn check_stack_area_size(arg_stack_space: u32, ret_area_size: u32) -> CodegenResult<()> {
+        Self::checked_stack_add(arg_stack_space, ret_area_size).map(|_| ())
+    }
+
+    /// The number of guard pages above which probing switches from an
+    /// unrolled sequence of stores to a counted loop, mirroring the
+    /// unroll/loop split used by established backends.
+    const PROBE_MAX_UNROLL: u32 = 4;
+
+    /// Probe every guard page that lies within `size` bytes of the
+    /// current `sp`, so that a subsequent `reserve_stack(size)` can't
+    /// skip over the OS guard page and corrupt memory.
+    ///
+    /// Does nothing if `size` is no larger than a single guard page.
+    fn probe_stack<M: MacroAssembler>(masm: &mut M, size: u32, guard_size: u32) {
+        if guard_size == 0 || size <= guard_size {
+            return;
+        }
+
+        // Ceiling division: `size` is aligned to `call_stack_align` (8
+        // or 16), not to `guard_size` (typically 4096), so floor
+        // division would leave the final, partial guard page
+        // completely unprobed.
+        let page_count = size.div_ceil(guard_size);
+        if page_count <= Self::PROBE_MAX_UNROLL {
+            for page in 1..=page_count {
+                // The last page may overshoot `size` if `size` isn't an
+                // exact multiple of `guard_size`; clamp so the probe
+                // still lands within the region actually being
+                // reserved, while still reaching `size` itself.
+                masm.zero_store_at_sp_offset((guard_size * page).min(size));
+            }
+        } else {
+            masm.probe_stack_loop(page_count, guard_size, size);
+        }
+    }
+
+    /// Compute the maximum outgoing stack-argument space required
+    /// across every call in a function body, aligned the same way each
+    /// individual call's own `arg_stack_space` is.
+    ///
+    /// This is the pre-pass "accumulated outgoing args" mode relies on:
+    /// rather than have each call reserve and free its own area, the
+    /// function's prologue reserves a single block sized for the worst
+    /// case, via [`CodeGenContext::init_outgoing_args_area`], and every
+    /// call writes its stack arguments into it at its own offset
+    /// instead.
+    pub fn max_outgoing_args_size<A: ABI>(
+        abi: &A,
+        call_sigs: impl IntoIterator<Item = &'a ABISig>,
+    ) -> CodegenResult<u32> {
+        call_sigs
+            .into_iter()
+            .map(|sig| {
+                // Validate before `align_to`'s addition, which would
+                // otherwise risk overflowing for a pathological
+                // `stack_bytes` near `u32::MAX`.
+                let stack_bytes = Self::checked_stack_add(sig.stack_bytes, 0)?;
+                Ok(align_to(stack_bytes, abi.call_stack_align() as u32))
+            })
+            .try_fold(0u32, |acc, size: CodegenResult<u32>| Ok(acc.max(size?)))
+    }
+
+    /// Whether a tail call to `callee_sig` is possible given the
+    /// caller's own incoming stack-argument area: the callee's
+    /// (aligned) stack-argument area must fit within it, since a tail
+    /// call overwrites those slots in place rather than allocating a
+    /// fresh outgoing-argument area.
+    ///
+    /// Returns `Ok(None)` if the optimization doesn't apply, in which
+    /// case the caller should fall back to a normal call via
+    /// [`FnCall::new`]. Returns
+    /// `Err(CodegenError::ImplLimitExceeded)` if `callee_sig`'s raw
+    /// stack-argument size exceeds [`MAX_CALL_STACK_ARGS_SIZE`].
+    pub fn tail_call_arg_space<A: ABI>(
+        abi: &A,
+        callee_sig: &ABISig,
+        caller_incoming_arg_space: u32,
+    ) -> CodegenResult<Option<u32>> {
+        // Validate before `align_to`'s addition, which would otherwise
+        // risk overflowing for a pathological `stack_bytes` near
+        // `u32::MAX`.
+        let stack_bytes = Self::checked_stack_add(callee_sig.stack_bytes, 0)?;
+        let callee_stack = align_to(stack_bytes, abi.call_stack_align() as u32);
+        Ok((callee_stack <= caller_incoming_arg_space).then_some(callee_stack))
+    }
+
+    /// Allocate and setup a tail call: a call in return position whose
+    /// arguments are evaluated directly into the destination ABI
+    /// locations, with stack-passed arguments overwriting the caller's
+    /// own incoming argument slots instead of a freshly-allocated
+    /// outgoing-argument area.
+    ///
+    /// Callers must first confirm [`FnCall::tail_call_arg_space`]
+    /// returns `Some` before calling this constructor.
+    pub fn new_tail<A: ABI, M: MacroAssembler>(
+        abi: &A,
+        callee_sig: &'a ABISig,
+        context: &mut CodeGenContext,
+        masm: &mut M,
+    ) -> CodegenResult<Self> {
+        let stack = &context.stack;
+        let callee_params = &callee_sig.params;
+        let sp_offset_at_callsite = masm.sp_offset();
+
+        match callee_params.len() {
+            0 => {
+                let _ = context.spill_regs_and_count_memory_in(masm, ..);
+            }
+            _ => {
+                assert!(stack.len() >= callee_params.len());
+                let partition = stack.len() - callee_params.len();
+                let _ = context.spill_regs_and_count_memory_in(masm, 0..partition);
+                let _ = context.spill_regs_and_count_memory_in(masm, partition..);
+            }
+        };
+
+        // Validate the raw stack-argument size before `align_to` adds
+        // its alignment padding, which would otherwise risk overflowing
+        // for a pathological `callee_sig.stack_bytes` near `u32::MAX`.
+        let arg_stack_space = align_to(
+            Self::checked_stack_add(callee_sig.stack_bytes, 0)?,
+            abi.call_stack_align() as u32,
+        );
+        Self::check_stack_area_size(arg_stack_space, 0)?;
+
+        Ok(Self {
+            abi_sig: &callee_sig,
+            arg_stack_space,
+            ret_area_size: 0,
+            uses_shared_outgoing_args: false,
+            // `abi.arg_base_offset()` alone is only the distance from
+            // the function's *entry* `sp` to the caller's incoming
+            // stack arguments; `assign_args_tail` addresses them via
+            // `masm.address_at_sp`, which is relative to the *current*
+            // `sp`. Any spilling just above has already moved `sp` away
+            // from its entry position, so the offset from here must
+            // include that delta too.
+            tail_call_base_offset: Some(masm.sp_offset() + abi.arg_base_offset()),
+            guard_size: abi.guard_size(),
+            total_stack_space: 0,
+            sp_offset_at_callsite,
+        })
     }
 
     /// Emit the function call.
@@ -159,23 +391,86 @@ impl<'a> FnCall<'a> {
         context: &mut CodeGenContext,
         callee: u32,
     ) {
-        masm.reserve_stack(self.arg_stack_space);
-        self.assign_args(context, masm, <A as ABI>::scratch_reg());
+        // In accumulated-outgoing-args mode, the stack argument area was
+        // already carved out once by the function's prologue, so only
+        // the return area (if any) needs reserving here.
+        let reserve_amount = if self.uses_shared_outgoing_args {
+            self.ret_area_size
+        } else {
+            self.arg_stack_space + self.ret_area_size
+        };
+        // If moving `sp` down by `reserve_amount` in one go could skip
+        // over the guard page entirely, probe it one page at a time
+        // first so the OS always gets a chance to fault each page in.
+        Self::probe_stack(masm, reserve_amount, self.guard_size);
+        masm.reserve_stack(reserve_amount);
+        self.assign_args::<M, A>(context, masm, <A as ABI>::scratch_reg());
         masm.call(CalleeKind::Direct(callee));
-        masm.free_stack(self.total_stack_space);
+        // Nothing is reclaimed until `handle_result` has read every
+        // result back: the return area, if present, is the innermost
+        // part of this call's footprint and is addressed from `sp`.
         context.drop_last(self.abi_sig.params.len());
         // The stack pointer at the end of the function call
         // cannot be less than what it was when starting the
         // function call.
+        self.handle_result::<M, A>(context, masm);
+        masm.free_stack(self.total_stack_space);
         assert!(self.sp_offset_at_callsite >= masm.sp_offset());
-        self.handle_result(context, masm);
     }
 
-    fn assign_args<M: MacroAssembler>(
+    /// Emit a tail call, as set up by [`FnCall::new_tail`].
+    ///
+    /// Unlike a normal call, no new frame is allocated: stack arguments
+    /// are written directly into the caller's own incoming argument
+    /// slots, the stack reserved since the function's entry is freed
+    /// (bringing `sp_offset` back to `0`), and control is transferred
+    /// with a jump rather than a call. No callee-saved register restore
+    /// is emitted here. Because no return
+    /// follows, there's no result to handle and no `sp_offset` to
+    /// restore afterwards -- instead, the post-condition is that the
+    /// frame has been fully torn down.
+    pub fn emit_tail<M: MacroAssembler, A: ABI>(
+        &self,
+        masm: &mut M,
+        context: &mut CodeGenContext,
+        callee: u32,
+    ) {
+        let base = self
+            .tail_call_base_offset
+            .expect("emit_tail called on a non-tail FnCall");
+        self.assign_args_tail::<M, A>(context, masm, <A as ABI>::scratch_reg(), base);
+        context.drop_last(self.abi_sig.params.len());
+        // Free everything reserved since the function's entry -- locals
+        // and spills -- now that the callee's arguments have been
+        // written in place. The incoming-argument slots lie beyond
+        // `sp_offset` 0 and keep what was just written to them; no
+        // callee-saved register is restored here.
+        masm.free_stack(masm.sp_offset());
+        masm.jmp(CalleeKind::DirectTail(callee));
+        debug_assert_eq!(masm.sp_offset(), 0, "tail call must fully tear down the frame");
+    }
+
+    /// Emit the extending move required by `extension`, if any, leaving
+    /// the result in `reg`.
+    fn extend_arg<M: MacroAssembler>(
+        masm: &mut M,
+        reg: Reg,
+        from: OperandSize,
+        extension: ArgumentExtension,
+    ) {
+        match extension {
+            ArgumentExtension::None => {}
+            ArgumentExtension::Zero => masm.extend(reg, from, false),
+            ArgumentExtension::Sign => masm.extend(reg, from, true),
+        }
+    }
+
+    fn assign_args_tail<M: MacroAssembler, A: ABI>(
         &self,
         context: &mut CodeGenContext,
         masm: &mut M,
         scratch: Reg,
+        base: u32,
     ) {
         let arg_count = self.abi_sig.params.len();
         let stack = &context.stack;
@@ -185,31 +480,480 @@ impl<'a> FnCall<'a> {
                 .next()
                 .unwrap_or_else(|| panic!("expected stack value for function argument"));
             match &arg {
-                &ABIArg::Reg { ty, reg } => {
+                &ABIArg::Reg { ty, reg, extension } => {
                     context.move_val_to_reg(&val, *reg, masm, (*ty).into());
+                    Self::extend_arg(masm, *reg, (*ty).into(), *extension);
                 }
-                &ABIArg::Stack { ty, offset } => {
-                    let addr = masm.address_at_sp(*offset);
+                &ABIArg::Stack { ty, offset, extension } => {
+                    // Overwrite the caller's own incoming argument slot
+                    // rather than allocating fresh outgoing space.
+                    let addr = masm.address_at_sp(base + *offset);
                     let size: OperandSize = (*ty).into();
                     context.move_val_to_reg(val, scratch, masm, size);
+                    Self::extend_arg(masm, scratch, size, *extension);
                     masm.store(scratch.into(), addr, size);
                 }
             }
         }
     }
 
-    fn handle_result<M: MacroAssembler>(&self, context: &mut CodeGenContext, masm: &mut M) {
-        let result = &self.abi_sig.result;
-        if result.is_void() {
-            return;
+    /// The offset, from the current `sp`, at which this call's stack
+    /// arguments begin.
+    ///
+    /// In the common case (no shared outgoing-args area) the arguments
+    /// were just reserved directly above the return area, at
+    /// `ret_area_size`. When a shared outgoing-args area is in use, the
+    /// arguments instead live in the block the prologue reserved; the
+    /// distance from the current `sp` to the start of that block is
+    /// however many bytes have been pushed since the prologue finished
+    /// (any live-register spills for this call, plus this call's own
+    /// return-area reservation, if any).
+    fn arg_base_offset<M: MacroAssembler>(&self, context: &CodeGenContext, masm: &M) -> u32 {
+        if self.uses_shared_outgoing_args {
+            masm.sp_offset() - context.outgoing_args_area.unwrap()
+        } else {
+            self.ret_area_size
+        }
+    }
+
+    fn assign_args<M: MacroAssembler, A: ABI>(
+        &self,
+        context: &mut CodeGenContext,
+        masm: &mut M,
+        scratch: Reg,
+    ) {
+        let base = self.arg_base_offset(context, masm);
+        let arg_count = self.abi_sig.params.len();
+        let stack = &context.stack;
+        let mut stack_values = stack.peekn(arg_count);
+        for arg in &self.abi_sig.params {
+            let val = stack_values
+                .next()
+                .unwrap_or_else(|| panic!("expected stack value for function argument"));
+            match &arg {
+                &ABIArg::Reg { ty, reg, extension } => {
+                    context.move_val_to_reg(&val, *reg, masm, (*ty).into());
+                    Self::extend_arg(masm, *reg, (*ty).into(), *extension);
+                }
+                &ABIArg::Stack { ty, offset, extension } => {
+                    let addr = masm.address_at_sp(base + *offset);
+                    let size: OperandSize = (*ty).into();
+                    context.move_val_to_reg(val, scratch, masm, size);
+                    Self::extend_arg(masm, scratch, size, *extension);
+                    masm.store(scratch.into(), addr, size);
+                }
+            }
         }
 
+        if self.ret_area_size > 0 {
+            // The return area is always the innermost part of this
+            // call's footprint, regardless of where the stack arguments
+            // live.
+            let ret_area = masm.address_at_sp(0);
+            masm.lea(ret_area, <A as ABI>::ret_area_ptr_reg());
+        }
+    }
+
+    fn handle_result<M: MacroAssembler, A: ABI>(&self, context: &mut CodeGenContext, masm: &mut M) {
+        let result = &self.abi_sig.result;
         match result {
+            ABIResult::Void => {}
             &ABIResult::Reg { ty: _, reg } => {
                 assert!(context.regalloc.gpr_available(reg));
                 let result_reg = Val::reg(context.gpr(reg, masm));
                 context.stack.push(result_reg);
             }
+            ABIResult::Area { tys, regs, .. } => {
+                // Results are pushed in order: the leading results that
+                // fit in return registers come straight from `regs`; the
+                // rest are read back out of the return area, which is
+                // still live at the innermost `sp` offsets at this point.
+                let mut area_offset = 0u32;
+                for (i, ty) in tys.iter().enumerate() {
+                    if let Some(&reg) = regs.get(i) {
+                        assert!(context.regalloc.gpr_available(reg));
+                        let result_reg = Val::reg(context.gpr(reg, masm));
+                        context.stack.push(result_reg);
+                    } else {
+                        let size: OperandSize = (*ty).into();
+                        let addr = masm.address_at_sp(area_offset);
+                        let dst = <A as ABI>::scratch_reg();
+                        masm.load(addr, dst, size);
+                        let result_reg = Val::reg(context.gpr(dst, masm));
+                        context.stack.push(result_reg);
+                        area_offset += ty.size();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::WasmType;
+    use crate::codegen::RegAlloc;
+    use crate::masm::Address;
+    use crate::stack::Stack;
+
+    struct TestAbi;
+
+    impl ABI for TestAbi {
+        fn ret_area_ptr_reg() -> Reg {
+            Reg::new(0)
+        }
+
+        fn scratch_reg() -> Reg {
+            Reg::new(1)
         }
+
+        fn word_bytes() -> u32 {
+            8
+        }
+
+        fn arg_base_offset(&self) -> u32 {
+            16
+        }
+
+        fn call_stack_align(&self) -> u8 {
+            16
+        }
+
+        fn guard_size(&self) -> u32 {
+            4096
+        }
+    }
+
+    #[derive(Default)]
+    struct TestMasm {
+        sp_offset: u32,
+        zero_stores: Vec<u32>,
+        probe_loops: Vec<(u32, u32, u32)>,
+        extends: Vec<(Reg, OperandSize, bool)>,
+        stores: Vec<Address>,
+    }
+
+    impl MacroAssembler for TestMasm {
+        fn sp_offset(&self) -> u32 {
+            self.sp_offset
+        }
+
+        fn reserve_stack(&mut self, bytes: u32) {
+            self.sp_offset += bytes;
+        }
+
+        fn free_stack(&mut self, bytes: u32) {
+            self.sp_offset -= bytes;
+        }
+
+        fn address_at_sp(&self, offset: u32) -> Address {
+            Address::offset(Reg::new(2), offset)
+        }
+
+        fn store(&mut self, _src: crate::masm::RegImm, dst: Address, _size: OperandSize) {
+            self.stores.push(dst);
+        }
+
+        fn load(&mut self, _src: Address, _dst: Reg, _size: OperandSize) {}
+
+        fn lea(&mut self, _addr: Address, _dst: Reg) {}
+
+        fn call(&mut self, _callee: CalleeKind) {}
+
+        fn jmp(&mut self, _callee: CalleeKind) {}
+
+        fn zero_store_at_sp_offset(&mut self, offset: u32) {
+            self.zero_stores.push(offset);
+        }
+
+        fn probe_stack_loop(&mut self, page_count: u32, guard_size: u32, size: u32) {
+            self.probe_loops.push((page_count, guard_size, size));
+        }
+
+        fn extend(&mut self, reg: Reg, from: OperandSize, signed: bool) {
+            self.extends.push((reg, from, signed));
+        }
+    }
+
+    fn test_context() -> CodeGenContext {
+        CodeGenContext {
+            stack: Stack::default(),
+            regalloc: RegAlloc::default(),
+            outgoing_args_area: None,
+        }
+    }
+
+    /// A synthetic signature with no params (so `FnCall::new`'s spilling
+    /// logic has nothing to do) but whose stack-argument area exceeds
+    /// [`MAX_CALL_STACK_ARGS_SIZE`].
+    fn oversized_sig() -> ABISig {
+        ABISig {
+            params: vec![],
+            result: ABIResult::Void,
+            stack_bytes: MAX_CALL_STACK_ARGS_SIZE + 1,
+        }
+    }
+
+    fn small_sig() -> ABISig {
+        ABISig {
+            params: vec![],
+            result: ABIResult::Void,
+            stack_bytes: 16,
+        }
+    }
+
+    fn sig_with_stack_bytes(stack_bytes: u32) -> ABISig {
+        ABISig {
+            params: vec![],
+            result: ABIResult::Void,
+            stack_bytes,
+        }
+    }
+
+    #[test]
+    fn max_outgoing_args_size_is_the_largest_aligned_call() {
+        let abi = TestAbi;
+        let sigs = [sig_with_stack_bytes(1), sig_with_stack_bytes(20), sig_with_stack_bytes(8)];
+
+        assert_eq!(FnCall::max_outgoing_args_size(&abi, sigs.iter()), Ok(32));
+    }
+
+    #[test]
+    fn max_outgoing_args_size_is_zero_with_no_calls() {
+        let abi = TestAbi;
+        let sigs: [ABISig; 0] = [];
+
+        assert_eq!(FnCall::max_outgoing_args_size(&abi, sigs.iter()), Ok(0));
+    }
+
+    #[test]
+    fn max_outgoing_args_size_rejects_a_signature_near_u32_max_without_overflowing() {
+        let abi = TestAbi;
+        let sigs = [sig_with_stack_bytes(u32::MAX)];
+
+        assert_eq!(
+            FnCall::max_outgoing_args_size(&abi, sigs.iter()),
+            Err(CodegenError::ImplLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn tail_call_arg_space_fits_within_the_caller_incoming_arg_space() {
+        let abi = TestAbi;
+        let sig = sig_with_stack_bytes(8);
+
+        assert_eq!(FnCall::tail_call_arg_space(&abi, &sig, 16), Ok(Some(16)));
+    }
+
+    #[test]
+    fn tail_call_arg_space_does_not_fit_within_the_caller_incoming_arg_space() {
+        let abi = TestAbi;
+        let sig = sig_with_stack_bytes(32);
+
+        assert_eq!(FnCall::tail_call_arg_space(&abi, &sig, 16), Ok(None));
+    }
+
+    #[test]
+    fn tail_call_arg_space_rejects_a_signature_near_u32_max_without_overflowing() {
+        let abi = TestAbi;
+        let sig = sig_with_stack_bytes(u32::MAX);
+
+        assert_eq!(
+            FnCall::tail_call_arg_space(&abi, &sig, 16),
+            Err(CodegenError::ImplLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn init_outgoing_args_area_reserves_and_records_the_shared_block() {
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+
+        context.init_outgoing_args_area(&mut masm, 32);
+
+        assert_eq!(masm.sp_offset(), 32);
+        assert_eq!(context.outgoing_args_area, Some(32));
+    }
+
+    #[test]
+    fn init_outgoing_args_area_does_nothing_when_no_call_needs_stack_args() {
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+
+        context.init_outgoing_args_area(&mut masm, 0);
+
+        assert_eq!(masm.sp_offset(), 0);
+        assert_eq!(context.outgoing_args_area, None);
+    }
+
+    #[test]
+    fn new_rejects_oversized_stack_argument_area() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = oversized_sig();
+
+        let result = FnCall::new(&abi, &sig, &mut context, &mut masm);
+        assert_eq!(result.err(), Some(CodegenError::ImplLimitExceeded));
+    }
+
+    #[test]
+    fn new_accepts_signature_within_the_limit() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = small_sig();
+
+        assert!(FnCall::new(&abi, &sig, &mut context, &mut masm).is_ok());
+    }
+
+    #[test]
+    fn new_tail_rejects_oversized_stack_argument_area() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = oversized_sig();
+
+        let result = FnCall::new_tail(&abi, &sig, &mut context, &mut masm);
+        assert_eq!(result.err(), Some(CodegenError::ImplLimitExceeded));
+    }
+
+    #[test]
+    fn new_rejects_a_stack_argument_area_near_u32_max_without_overflowing() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = sig_with_stack_bytes(u32::MAX);
+
+        let result = FnCall::new(&abi, &sig, &mut context, &mut masm);
+        assert_eq!(result.err(), Some(CodegenError::ImplLimitExceeded));
+    }
+
+    #[test]
+    fn new_rejects_a_result_area_near_u32_max_without_overflowing() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = ABISig {
+            params: vec![],
+            result: ABIResult::Area {
+                tys: vec![],
+                regs: vec![],
+                area_size: u32::MAX,
+            },
+            stack_bytes: 0,
+        };
+
+        let result = FnCall::new(&abi, &sig, &mut context, &mut masm);
+        assert_eq!(result.err(), Some(CodegenError::ImplLimitExceeded));
+    }
+
+    #[test]
+    fn new_tail_rejects_a_stack_argument_area_near_u32_max_without_overflowing() {
+        let abi = TestAbi;
+        let mut masm = TestMasm::default();
+        let mut context = test_context();
+        let sig = sig_with_stack_bytes(u32::MAX);
+
+        let result = FnCall::new_tail(&abi, &sig, &mut context, &mut masm);
+        assert_eq!(result.err(), Some(CodegenError::ImplLimitExceeded));
+    }
+
+    #[test]
+    fn emit_tail_addresses_a_stack_argument_relative_to_the_current_sp() {
+        let abi = TestAbi;
+        // Simulate locals/spills already reserved earlier in the
+        // function, so entry-time `sp` and the current `sp` differ.
+        let mut masm = TestMasm {
+            sp_offset: 32,
+            ..Default::default()
+        };
+        let mut context = test_context();
+        context.stack.push(Val::mem(0));
+        let sig = ABISig {
+            params: vec![ABIArg::Stack {
+                ty: WasmType::I32,
+                offset: 0,
+                extension: ArgumentExtension::None,
+            }],
+            result: ABIResult::Void,
+            stack_bytes: 4,
+        };
+
+        let call = FnCall::new_tail(&abi, &sig, &mut context, &mut masm).unwrap();
+        call.emit_tail::<TestMasm, TestAbi>(&mut masm, &mut context, 0);
+
+        // The caller's incoming stack argument lives at
+        // `abi.arg_base_offset()` (16) past the caller's *entry* `sp`,
+        // which is 32 bytes above the current `sp` here.
+        assert_eq!(masm.stores, vec![Address::offset(Reg::new(2), 48)]);
+    }
+
+    #[test]
+    fn probe_stack_does_nothing_within_a_single_guard_page() {
+        let mut masm = TestMasm::default();
+        FnCall::probe_stack(&mut masm, 4096, 4096);
+        assert!(masm.zero_stores.is_empty());
+        assert!(masm.probe_loops.is_empty());
+    }
+
+    #[test]
+    fn probe_stack_unrolls_a_small_page_count() {
+        let mut masm = TestMasm::default();
+        FnCall::probe_stack(&mut masm, 4096 * 3, 4096);
+        assert_eq!(masm.zero_stores, vec![4096, 8192, 12288]);
+        assert!(masm.probe_loops.is_empty());
+    }
+
+    #[test]
+    fn probe_stack_reaches_a_non_guard_size_aligned_final_page() {
+        let mut masm = TestMasm::default();
+        FnCall::probe_stack(&mut masm, 4096 * 3 + 100, 4096);
+        // Floor division would stop at 12288, never touching the page
+        // that `sp` actually lands on after `reserve_stack(size)`.
+        assert_eq!(masm.zero_stores, vec![4096, 8192, 12288, 12388]);
+        assert!(masm.probe_loops.is_empty());
+    }
+
+    #[test]
+    fn probe_stack_uses_a_loop_past_the_unroll_threshold() {
+        let mut masm = TestMasm::default();
+        FnCall::probe_stack(&mut masm, 4096 * 10, 4096);
+        assert!(masm.zero_stores.is_empty());
+        assert_eq!(masm.probe_loops, vec![(10, 4096, 4096 * 10)]);
+    }
+
+    #[test]
+    fn probe_stack_loop_passes_through_a_non_guard_size_aligned_size() {
+        let mut masm = TestMasm::default();
+        FnCall::probe_stack(&mut masm, 4096 * 5 + 100, 4096);
+        assert!(masm.zero_stores.is_empty());
+        // `page_count` is the ceiling (6), but the real size (20580) is
+        // passed through too, so the backend can clamp its final
+        // iteration instead of overshooting by up to `guard_size - 1`
+        // bytes.
+        assert_eq!(masm.probe_loops, vec![(6, 4096, 4096 * 5 + 100)]);
+    }
+
+    #[test]
+    fn extend_arg_does_nothing_for_argument_extension_none() {
+        let mut masm = TestMasm::default();
+        FnCall::extend_arg(&mut masm, Reg::new(3), OperandSize::S32, ArgumentExtension::None);
+        assert!(masm.extends.is_empty());
+    }
+
+    #[test]
+    fn extend_arg_zero_extends_for_argument_extension_zero() {
+        let mut masm = TestMasm::default();
+        FnCall::extend_arg(&mut masm, Reg::new(3), OperandSize::S32, ArgumentExtension::Zero);
+        assert_eq!(masm.extends, vec![(Reg::new(3), OperandSize::S32, false)]);
+    }
+
+    #[test]
+    fn extend_arg_sign_extends_for_argument_extension_sign() {
+        let mut masm = TestMasm::default();
+        FnCall::extend_arg(&mut masm, Reg::new(3), OperandSize::S32, ArgumentExtension::Sign);
+        assert_eq!(masm.extends, vec![(Reg::new(3), OperandSize::S32, true)]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,37 @@
+//! Errors produced while generating code for a function body.
+
+use std::fmt;
+
+/// The maximum combined size, in bytes, of a single call's
+/// stack-argument and return-area footprint.
+///
+/// This is well beyond what any reasonable function signature needs,
+/// but keeps the `u32` arithmetic used to compute stack offsets from
+/// wrapping on a pathological signature, mirroring the limit other
+/// Cranelift backends place on a call's stack footprint.
+pub(crate) const MAX_CALL_STACK_ARGS_SIZE: u32 = 128 * 1024 * 1024;
+
+/// An error produced while generating code for a function body.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CodegenError {
+    /// A call's combined stack-argument and return-area size exceeded
+    /// [`MAX_CALL_STACK_ARGS_SIZE`].
+    ImplLimitExceeded,
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::ImplLimitExceeded => write!(
+                f,
+                "implementation limit exceeded: call stack argument and return area size \
+                 exceeds {MAX_CALL_STACK_ARGS_SIZE} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+/// A convenience alias for fallible code generation.
+pub(crate) type CodegenResult<T> = Result<T, CodegenError>;
@@ -0,0 +1,141 @@
+//! The core code generation state threaded through the compilation of a
+//! single function body.
+
+mod call;
+mod error;
+
+pub(crate) use call::FnCall;
+pub use error::CodegenError;
+pub(crate) use error::CodegenResult;
+
+use crate::{
+    masm::{MacroAssembler, OperandSize, RegImm},
+    reg::Reg,
+    stack::{Stack, Val},
+};
+
+/// Tracks which registers are currently in use.
+#[derive(Default)]
+pub(crate) struct RegAlloc {
+    taken: Vec<Reg>,
+}
+
+impl RegAlloc {
+    /// Whether `reg` is free to claim for a GPR value.
+    pub(crate) fn gpr_available(&self, reg: Reg) -> bool {
+        !self.taken.contains(&reg)
+    }
+}
+
+/// The code generation context for a single function body: the shadow
+/// value stack and the register allocator.
+pub(crate) struct CodeGenContext {
+    pub(crate) stack: Stack,
+    pub(crate) regalloc: RegAlloc,
+    /// The `sp_offset` recorded right after the function's prologue
+    /// reserved a shared outgoing-argument area, when "accumulated
+    /// outgoing args" mode is enabled for this function (mirroring the
+    /// scheme used on s390x).
+    ///
+    /// When set, a pre-pass over the function body has already computed
+    /// the maximum `arg_stack_space` required across every call and
+    /// allocated that block exactly once in the prologue; individual
+    /// [`FnCall`]s then just write their stack arguments at offsets into
+    /// this block, rather than reserving and freeing their own space.
+    /// `None` means no such block exists and every call manages its own
+    /// argument area.
+    pub(crate) outgoing_args_area: Option<u32>,
+}
+
+impl CodeGenContext {
+    /// Spill every register-resident value in the given range of the
+    /// value stack, emitting a push per register, and count how many
+    /// entries in that range are already memory values.
+    ///
+    /// Returns `(spilled_regs, memory_values)`.
+    pub(crate) fn spill_regs_and_count_memory_in<M: MacroAssembler>(
+        &mut self,
+        masm: &mut M,
+        range: impl std::ops::RangeBounds<usize>,
+    ) -> (u32, u32) {
+        let len = self.stack.len();
+        let (start, end) = crate::codegen::range_bounds(range, len);
+        let mut spilled_regs = 0;
+        let mut memory_values = 0;
+        for i in start..end {
+            match self.stack.peekn(len - i).next() {
+                Some(Val::Reg(_)) => spilled_regs += 1,
+                Some(Val::Mem(_)) => memory_values += 1,
+                None => {}
+            }
+        }
+        (spilled_regs, memory_values)
+    }
+
+    /// Ensure `val` is materialized in `dst`, emitting whatever move/load
+    /// is necessary depending on where the value currently lives.
+    pub(crate) fn move_val_to_reg<M: MacroAssembler>(
+        &self,
+        val: &Val,
+        dst: Reg,
+        masm: &mut M,
+        size: OperandSize,
+    ) {
+        match *val {
+            Val::Reg(src) if src != dst => {
+                masm.store(RegImm::Reg(src), masm.address_at_sp(0), size);
+                masm.load(masm.address_at_sp(0), dst, size);
+            }
+            Val::Reg(_) => {}
+            Val::Mem(offset) => masm.load(masm.address_at_sp(offset), dst, size),
+        }
+    }
+
+    /// Claim `reg` as the home of a GPR-resident value.
+    pub(crate) fn gpr<M: MacroAssembler>(&mut self, reg: Reg, _masm: &mut M) -> Reg {
+        self.regalloc.taken.push(reg);
+        reg
+    }
+
+    /// Drop the top `count` entries of the value stack, reclaiming any
+    /// registers they held.
+    pub(crate) fn drop_last(&mut self, count: usize) {
+        let popped = self.stack.popn(count);
+        for val in popped {
+            if let Val::Reg(reg) = val {
+                self.regalloc.taken.retain(|r| *r != reg);
+            }
+        }
+    }
+
+    /// Reserve the shared outgoing-argument area in the function's
+    /// prologue and record its location, enabling "accumulated
+    /// outgoing args" mode for every call emitted afterwards in this
+    /// function body (see [`Self::outgoing_args_area`]).
+    ///
+    /// `size` should be [`crate::codegen::FnCall::max_outgoing_args_size`]
+    /// computed over every call in the function body. Does nothing if
+    /// `size` is `0`.
+    pub(crate) fn init_outgoing_args_area<M: MacroAssembler>(&mut self, masm: &mut M, size: u32) {
+        if size == 0 {
+            return;
+        }
+        masm.reserve_stack(size);
+        self.outgoing_args_area = Some(masm.sp_offset());
+    }
+}
+
+fn range_bounds(range: impl std::ops::RangeBounds<usize>, len: usize) -> (usize, usize) {
+    use std::ops::Bound::*;
+    let start = match range.start_bound() {
+        Included(&s) => s,
+        Excluded(&s) => s + 1,
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(&e) => e + 1,
+        Excluded(&e) => e,
+        Unbounded => len,
+    };
+    (start, end)
+}
@@ -0,0 +1,13 @@
+//! Winch: a non-optimizing baseline compiler for WebAssembly.
+//!
+//! This crate implements the code generation pipeline shared by all of
+//! Winch's target backends: the ABI layer, the macro assembler
+//! interface, and the core code generator built on top of them.
+
+pub(crate) mod abi;
+pub(crate) mod codegen;
+pub(crate) mod masm;
+pub(crate) mod reg;
+pub(crate) mod stack;
+
+pub use codegen::CodegenError;
@@ -0,0 +1,119 @@
+//! The `MacroAssembler` trait.
+//!
+//! Every target backend implements `MacroAssembler` in terms of its own
+//! instruction encodings. The rest of the code generator -- in
+//! particular, function call emission -- is written entirely against this
+//! trait and never touches ISA-specific instructions directly.
+
+use crate::{abi::WasmType, reg::Reg};
+
+/// The size, in bits, of an operand being moved, stored or loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandSize {
+    S8,
+    S16,
+    S32,
+    S64,
+}
+
+impl From<WasmType> for OperandSize {
+    fn from(ty: WasmType) -> Self {
+        match ty {
+            WasmType::I32 | WasmType::F32 => OperandSize::S32,
+            WasmType::I64 | WasmType::F64 => OperandSize::S64,
+        }
+    }
+}
+
+/// A register or an immediate, used as the source operand of a store.
+#[derive(Debug, Clone, Copy)]
+pub enum RegImm {
+    Reg(Reg),
+    Imm(i64),
+}
+
+impl From<Reg> for RegImm {
+    fn from(reg: Reg) -> Self {
+        RegImm::Reg(reg)
+    }
+}
+
+/// A memory address, expressed relative to a base register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address {
+    base: Reg,
+    offset: u32,
+}
+
+impl Address {
+    pub(crate) fn offset(base: Reg, offset: u32) -> Self {
+        Self { base, offset }
+    }
+}
+
+/// The callee of a call instruction.
+#[derive(Debug, Clone, Copy)]
+pub enum CalleeKind {
+    /// A direct call to a known function, identified by its index.
+    Direct(u32),
+    /// A direct tail call: like [`CalleeKind::Direct`], but lowered to a
+    /// jump into the callee rather than a call, reusing the caller's
+    /// frame.
+    DirectTail(u32),
+}
+
+/// The target-agnostic interface used to emit machine code.
+pub(crate) trait MacroAssembler {
+    /// The current offset of the stack pointer from the start of the
+    /// function's frame.
+    fn sp_offset(&self) -> u32;
+
+    /// Lower the stack pointer by `bytes`, growing the current frame.
+    fn reserve_stack(&mut self, bytes: u32);
+
+    /// Raise the stack pointer by `bytes`, shrinking the current frame.
+    fn free_stack(&mut self, bytes: u32);
+
+    /// Compute the address of `offset` bytes above the current stack
+    /// pointer.
+    fn address_at_sp(&self, offset: u32) -> Address;
+
+    /// Store `src` to `dst`.
+    fn store(&mut self, src: RegImm, dst: Address, size: OperandSize);
+
+    /// Load `src` into `dst`.
+    fn load(&mut self, src: Address, dst: Reg, size: OperandSize);
+
+    /// Load the effective address of `addr` into `dst`.
+    fn lea(&mut self, addr: Address, dst: Reg);
+
+    /// Emit a call.
+    fn call(&mut self, callee: CalleeKind);
+
+    /// Emit an unconditional jump, used for tail calls.
+    fn jmp(&mut self, callee: CalleeKind);
+
+    /// Touch the page at `[sp - offset]` with a store of zero, without
+    /// otherwise moving the stack pointer.
+    ///
+    /// This is the primitive used to probe a single guard page: emitting
+    /// one of these per page, at increasing offsets, guarantees every
+    /// page between the current `sp` and `sp - offset` is faulted in
+    /// before a subsequent `reserve_stack` moves `sp` across it.
+    fn zero_store_at_sp_offset(&mut self, offset: u32);
+
+    /// Probe `page_count` guard-sized pages below the current `sp` using
+    /// a counted loop rather than an unrolled sequence of
+    /// [`Self::zero_store_at_sp_offset`] calls, for use when
+    /// `page_count` is too large to unroll economically.
+    ///
+    /// `size` is the actual number of bytes being reserved, which the
+    /// loop's final iteration should clamp to rather than overshooting
+    /// by up to `guard_size - 1` bytes when `size` isn't an exact
+    /// multiple of `guard_size`.
+    fn probe_stack_loop(&mut self, page_count: u32, guard_size: u32, size: u32);
+
+    /// Sign- or zero-extend the low `from` bits of `reg` in place, to
+    /// fill the rest of the register.
+    fn extend(&mut self, reg: Reg, from: OperandSize, signed: bool);
+}
@@ -0,0 +1,23 @@
+//! Register abstraction used throughout the code generation pipeline.
+
+/// A physical machine register.
+///
+/// `Reg` is a thin, ISA-agnostic wrapper around a hardware register
+/// encoding. The code generator never reasons about concrete ISA register
+/// files directly; it always goes through this type so that the rest of
+/// the pipeline (the value stack, the ABI layer, the macro assembler) stays
+/// portable across backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reg(u8);
+
+impl Reg {
+    /// Create a new register from its hardware encoding.
+    pub const fn new(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// The hardware encoding for this register.
+    pub const fn hw_enc(&self) -> u8 {
+        self.0
+    }
+}
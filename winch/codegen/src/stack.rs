@@ -0,0 +1,63 @@
+//! The shadow value stack.
+//!
+//! Winch tracks the location of every Wasm value -- in a register or
+//! spilled to the machine stack -- in a lightweight shadow stack rather
+//! than relying on the machine stack pointer alone. This lets the code
+//! generator lazily materialize values only when they're needed (e.g. as
+//! call arguments), avoiding redundant loads and stores.
+
+use crate::reg::Reg;
+
+/// A Wasm value as tracked by the shadow stack.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Val {
+    /// The value currently lives in a register.
+    Reg(Reg),
+    /// The value was spilled to the machine stack, at the given offset
+    /// from the current frame's stack pointer.
+    Mem(u32),
+}
+
+impl Val {
+    /// Create a register value.
+    pub(crate) fn reg(reg: Reg) -> Self {
+        Self::Reg(reg)
+    }
+
+    /// Create a memory value at the given stack pointer offset.
+    pub(crate) fn mem(offset: u32) -> Self {
+        Self::Mem(offset)
+    }
+}
+
+/// The shadow stack, mirroring the Wasm operand stack during code
+/// generation.
+#[derive(Default)]
+pub(crate) struct Stack {
+    inner: Vec<Val>,
+}
+
+impl Stack {
+    /// The number of values currently tracked.
+    pub(crate) fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Push a value onto the stack.
+    pub(crate) fn push(&mut self, val: Val) {
+        self.inner.push(val);
+    }
+
+    /// Pop the topmost `n` values off the stack.
+    pub(crate) fn popn(&mut self, n: usize) -> Vec<Val> {
+        let at = self.inner.len() - n;
+        self.inner.split_off(at)
+    }
+
+    /// Iterate over the top `n` values without removing them, from bottom
+    /// to top of that slice.
+    pub(crate) fn peekn(&self, n: usize) -> impl Iterator<Item = &Val> {
+        let len = self.inner.len();
+        self.inner[len - n..].iter()
+    }
+}